@@ -0,0 +1,316 @@
+// A small two-way translator between Chip-8 opcodes and a human-readable
+// mnemonic syntax, e.g. `LD I, 0x300` / `LD V1, 0x10` / `DRW V0, V1, 6`.
+// This mirrors `Chip8::dump_to_stdout`'s raw hex view, but lets a developer
+// inspect (or hand-write) a ROM as assembly instead.
+
+use std::collections::HashMap;
+
+use super::Variant;
+
+// Parses one instruction per line, resolving `nnn`/`kk`/`Vx`/`n` fields and
+// symbolic labels (`loop:`) used by jumps and calls, into the matching
+// sequence of big-endian 2-byte opcodes. `variant` supplies the load
+// address (0x200, or 0x600 for ETI-660) that labels are resolved against,
+// matching wherever `Chip8::load_rom` will actually place the bytes this
+// function returns.
+pub fn assemble(source: &str, variant: Variant) -> Vec<u8> {
+    let lines: Vec<&str> = source.lines().collect();
+
+    // First pass: record the byte address of every label.
+    let mut labels = HashMap::new();
+    let mut address: u16 = variant.start_address() as u16;
+    for raw in &lines {
+        let line = strip_comment(raw).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(label) = line.strip_suffix(':') {
+            labels.insert(label.trim().to_string(), address);
+        } else {
+            address += 2;
+        }
+    }
+
+    // Second pass: encode each instruction, now that every label resolves.
+    let mut bytes = Vec::new();
+    for raw in &lines {
+        let line = strip_comment(raw).trim();
+        if line.is_empty() || line.ends_with(':') {
+            continue;
+        }
+
+        let opcode = encode_instruction(line, &labels);
+        bytes.push((opcode >> 8) as u8);
+        bytes.push((opcode & 0xFF) as u8);
+    }
+
+    bytes
+}
+
+// Decodes a byte stream back into the same mnemonic syntax `assemble`
+// accepts. Unrecognised opcodes are emitted as a `DW` (define word) literal
+// rather than panicking, since a ROM may contain raw data alongside code.
+pub fn disassemble(bytes: &[u8]) -> Vec<String> {
+    bytes
+        .chunks(2)
+        .map(|pair| {
+            let opcode = if pair.len() == 2 {
+                ((pair[0] as u16) << 8) | pair[1] as u16
+            } else {
+                (pair[0] as u16) << 8
+            };
+            decode_instruction(opcode)
+        })
+        .collect()
+}
+
+fn strip_comment(line: &str) -> &str {
+    match line.find(';') {
+        Some(index) => &line[..index],
+        None => line,
+    }
+}
+
+fn encode_instruction(line: &str, labels: &HashMap<String, u16>) -> u16 {
+    let (mnemonic, rest) = line.split_once(' ').unwrap_or((line, ""));
+    let operands: Vec<&str> = rest
+        .split(',')
+        .map(|operand| operand.trim())
+        .filter(|operand| !operand.is_empty())
+        .collect();
+
+    match mnemonic.to_uppercase().as_str() {
+        "CLS" => 0x00E0,
+        "RET" => 0x00EE,
+        "JP" if operands.len() == 2 => 0xB000 | resolve_addr(operands[1], labels),
+        "JP" => 0x1000 | resolve_addr(operands[0], labels),
+        "CALL" => 0x2000 | resolve_addr(operands[0], labels),
+        "SE" => skip_or_byte(0x5000, 0x3000, &operands),
+        "SNE" => skip_or_byte(0x9000, 0x4000, &operands),
+        "LD" => encode_ld(&operands, labels),
+        "ADD" => encode_add(&operands),
+        "OR" => 0x8001 | reg_reg(&operands),
+        "AND" => 0x8002 | reg_reg(&operands),
+        "XOR" => 0x8003 | reg_reg(&operands),
+        "SUB" => 0x8005 | reg_reg(&operands),
+        "SUBN" => 0x8007 | reg_reg(&operands),
+        "SHR" => 0x8006 | shift_reg(&operands),
+        "SHL" => 0x800E | shift_reg(&operands),
+        "RND" => 0xC000 | (parse_vx(operands[0]) as u16) << 8 | parse_byte(operands[1]) as u16,
+        "DRW" => {
+            let x = parse_vx(operands[0]);
+            let y = parse_vx(operands[1]);
+            let n = parse_byte(operands[2]);
+            0xD000 | (x as u16) << 8 | (y as u16) << 4 | n as u16
+        }
+        "SKP" => 0xE09E | (parse_vx(operands[0]) as u16) << 8,
+        "SKNP" => 0xE0A1 | (parse_vx(operands[0]) as u16) << 8,
+        // `disassemble` emits this for opcodes it can't otherwise name, so a
+        // raw data byte or unrecognised opcode round-trips back through
+        // `assemble` unchanged instead of panicking.
+        "DW" => parse_number(operands[0]),
+        _ => panic!("Unrecognised mnemonic: {}", mnemonic),
+    }
+}
+
+// Shared shape for SE/SNE, which take either `Vx, Vy` or `Vx, byte`.
+fn skip_or_byte(reg_opcode: u16, byte_opcode: u16, operands: &[&str]) -> u16 {
+    let x = parse_vx(operands[0]);
+    if let Some(y) = try_parse_vx(operands[1]) {
+        reg_opcode | (x as u16) << 8 | (y as u16) << 4
+    } else {
+        byte_opcode | (x as u16) << 8 | parse_byte(operands[1]) as u16
+    }
+}
+
+fn reg_reg(operands: &[&str]) -> u16 {
+    let x = parse_vx(operands[0]);
+    let y = parse_vx(operands[1]);
+    (x as u16) << 8 | (y as u16) << 4
+}
+
+// SHR/SHL accept an optional Vy (`SHR Vx {, Vy}`); it defaults to 0 when
+// omitted, matching how the Vy field is simply unused on variants that
+// shift Vx in place.
+fn shift_reg(operands: &[&str]) -> u16 {
+    let x = parse_vx(operands[0]);
+    let y = operands.get(1).map_or(0, |operand| parse_vx(operand));
+    (x as u16) << 8 | (y as u16) << 4
+}
+
+fn encode_ld(operands: &[&str], labels: &HashMap<String, u16>) -> u16 {
+    let dst = operands[0];
+    let src = operands[1];
+
+    if dst.eq_ignore_ascii_case("I") {
+        return 0xA000 | resolve_addr(src, labels);
+    }
+    if dst.eq_ignore_ascii_case("DT") {
+        return 0xF015 | (parse_vx(src) as u16) << 8;
+    }
+    if dst.eq_ignore_ascii_case("ST") {
+        return 0xF018 | (parse_vx(src) as u16) << 8;
+    }
+    if dst.eq_ignore_ascii_case("F") {
+        return 0xF029 | (parse_vx(src) as u16) << 8;
+    }
+    if dst.eq_ignore_ascii_case("B") {
+        return 0xF033 | (parse_vx(src) as u16) << 8;
+    }
+    if dst.eq_ignore_ascii_case("[I]") {
+        return 0xF055 | (parse_vx(src) as u16) << 8;
+    }
+    if src.eq_ignore_ascii_case("DT") {
+        return 0xF007 | (parse_vx(dst) as u16) << 8;
+    }
+    if src.eq_ignore_ascii_case("K") {
+        return 0xF00A | (parse_vx(dst) as u16) << 8;
+    }
+    if src.eq_ignore_ascii_case("[I]") {
+        return 0xF065 | (parse_vx(dst) as u16) << 8;
+    }
+
+    let x = parse_vx(dst);
+    if let Some(y) = try_parse_vx(src) {
+        0x8000 | (x as u16) << 8 | (y as u16) << 4
+    } else {
+        0x6000 | (x as u16) << 8 | parse_byte(src) as u16
+    }
+}
+
+fn encode_add(operands: &[&str]) -> u16 {
+    let dst = operands[0];
+    if dst.eq_ignore_ascii_case("I") {
+        return 0xF01E | (parse_vx(operands[1]) as u16) << 8;
+    }
+
+    let x = parse_vx(dst);
+    if let Some(y) = try_parse_vx(operands[1]) {
+        0x8004 | (x as u16) << 8 | (y as u16) << 4
+    } else {
+        0x7000 | (x as u16) << 8 | parse_byte(operands[1]) as u16
+    }
+}
+
+fn parse_vx(token: &str) -> u8 {
+    try_parse_vx(token).unwrap_or_else(|| panic!("Expected a register, found '{}'", token))
+}
+
+fn try_parse_vx(token: &str) -> Option<u8> {
+    let token = token.trim();
+    if token.len() >= 2 && (token.starts_with('V') || token.starts_with('v')) {
+        let register = u8::from_str_radix(&token[1..], 16).ok()?;
+        if register > 0xF {
+            panic!("Register out of range, found '{}'", token);
+        }
+        Some(register)
+    } else {
+        None
+    }
+}
+
+fn parse_byte(token: &str) -> u8 {
+    parse_number(token) as u8
+}
+
+fn parse_number(token: &str) -> u16 {
+    let token = token.trim();
+    if let Some(hex) = token.strip_prefix("0x").or_else(|| token.strip_prefix("0X")) {
+        u16::from_str_radix(hex, 16).expect("Invalid hex literal")
+    } else {
+        token.parse().expect("Invalid numeric literal")
+    }
+}
+
+fn resolve_addr(token: &str, labels: &HashMap<String, u16>) -> u16 {
+    let token = token.trim();
+    match labels.get(token) {
+        Some(&addr) => addr,
+        None => parse_number(token),
+    }
+}
+
+fn decode_instruction(opcode: u16) -> String {
+    let nnn = opcode & 0x0FFF;
+    let n = opcode & 0x000F;
+    let x = (opcode & 0x0F00) >> 8;
+    let y = (opcode & 0x00F0) >> 4;
+    let kk = opcode & 0x00FF;
+
+    match opcode & 0xF000 {
+        0x0000 => match opcode {
+            0x00E0 => "CLS".to_string(),
+            0x00EE => "RET".to_string(),
+            _ => format!("DW 0x{:04X}", opcode),
+        },
+        0x1000 => format!("JP 0x{:03X}", nnn),
+        0x2000 => format!("CALL 0x{:03X}", nnn),
+        0x3000 => format!("SE V{:X}, 0x{:02X}", x, kk),
+        0x4000 => format!("SNE V{:X}, 0x{:02X}", x, kk),
+        0x5000 => format!("SE V{:X}, V{:X}", x, y),
+        0x6000 => format!("LD V{:X}, 0x{:02X}", x, kk),
+        0x7000 => format!("ADD V{:X}, 0x{:02X}", x, kk),
+        0x8000 => match n {
+            0x0 => format!("LD V{:X}, V{:X}", x, y),
+            0x1 => format!("OR V{:X}, V{:X}", x, y),
+            0x2 => format!("AND V{:X}, V{:X}", x, y),
+            0x3 => format!("XOR V{:X}, V{:X}", x, y),
+            0x4 => format!("ADD V{:X}, V{:X}", x, y),
+            0x5 => format!("SUB V{:X}, V{:X}", x, y),
+            0x6 => format!("SHR V{:X}, V{:X}", x, y),
+            0x7 => format!("SUBN V{:X}, V{:X}", x, y),
+            0xE => format!("SHL V{:X}, V{:X}", x, y),
+            _ => format!("DW 0x{:04X}", opcode),
+        },
+        0x9000 => format!("SNE V{:X}, V{:X}", x, y),
+        0xA000 => format!("LD I, 0x{:03X}", nnn),
+        0xB000 => format!("JP V0, 0x{:03X}", nnn),
+        0xC000 => format!("RND V{:X}, 0x{:02X}", x, kk),
+        0xD000 => format!("DRW V{:X}, V{:X}, {}", x, y, n),
+        0xE000 => match kk {
+            0x9E => format!("SKP V{:X}", x),
+            0xA1 => format!("SKNP V{:X}", x),
+            _ => format!("DW 0x{:04X}", opcode),
+        },
+        0xF000 => match kk {
+            0x07 => format!("LD V{:X}, DT", x),
+            0x0A => format!("LD V{:X}, K", x),
+            0x15 => format!("LD DT, V{:X}", x),
+            0x18 => format!("LD ST, V{:X}", x),
+            0x1E => format!("ADD I, V{:X}", x),
+            0x29 => format!("LD F, V{:X}", x),
+            0x33 => format!("LD B, V{:X}", x),
+            0x55 => format!("LD [I], V{:X}", x),
+            0x65 => format!("LD V{:X}, [I]", x),
+            _ => format!("DW 0x{:04X}", opcode),
+        },
+        _ => format!("DW 0x{:04X}", opcode),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Variant;
+
+    #[test]
+    fn disassembling_an_unrecognised_opcode_round_trips_through_assemble() {
+        // 0xFFFF isn't a valid opcode, so disassemble falls back to DW.
+        let mnemonics = disassemble(&[0xFF, 0xFF]);
+        assert_eq!(mnemonics, vec!["DW 0xFFFF".to_string()]);
+
+        let bytes = assemble(&mnemonics.join("\n"), Variant::CosmacVip);
+        assert_eq!(bytes, vec![0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn assemble_resolves_labels_against_the_variants_start_address() {
+        let source = "LD V0, 0x01\nJP done\nLD V0, 0x02\ndone:\nLD V1, 0xAA\n";
+
+        let bytes = assemble(source, Variant::CosmacVip);
+        let mnemonics = disassemble(&bytes);
+
+        assert_eq!(mnemonics[1], "JP 0x206");
+    }
+}
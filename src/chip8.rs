@@ -1,19 +1,190 @@
 // Reference: http://devernay.free.fr/hacks/chip8/C8TECH10.HTM
 
+pub mod assembler;
+
 use std::fs::{self};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 // 2.1 - Memory
 // Most Chip-8 programs start at location 0x200 (512), but some begin at
 // 0x600 (1536). Programs beginning at 0x600 are intended for the ETI 660
 // computer.
 const NORMAL_START_INDEX: usize = 512;
-// const ETI_660_START_INDEX: usize = 1526;
+const ETI_660_START_INDEX: usize = 0x600;
+
+// Several Chip-8 derivatives disagree on the exact behaviour of a handful
+// of opcodes. `Variant` captures which behaviour this `Chip8` should follow,
+// the same way a 6502 core is often parameterized over NMOS vs CMOS quirks.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// The original COSMAC VIP interpreter.
+    CosmacVip,
+    /// The ETI-660, which loads programs at 0x600 instead of 0x200.
+    Eti660,
+    /// The later SUPER-CHIP interpreter.
+    SuperChip,
+}
+
+impl Variant {
+    fn start_address(&self) -> usize {
+        match self {
+            Variant::Eti660 => ETI_660_START_INDEX,
+            Variant::CosmacVip | Variant::SuperChip => NORMAL_START_INDEX,
+        }
+    }
+
+    // 8xy6/8xyE: the COSMAC VIP shifts Vy and stores the result in Vx;
+    // SUPER-CHIP shifts Vx in place, ignoring Vy.
+    fn shift_in_place(&self) -> bool {
+        matches!(self, Variant::SuperChip)
+    }
+
+    // Fx55/Fx65: the COSMAC VIP leaves I pointing just past the last
+    // register stored/loaded; SUPER-CHIP leaves I unchanged.
+    fn load_store_increments_i(&self) -> bool {
+        !matches!(self, Variant::SuperChip)
+    }
+
+    // Bnnn: the COSMAC VIP always adds V0; SUPER-CHIP uses Vx, where x is
+    // the highest nibble of nnn.
+    fn jump_uses_vx(&self) -> bool {
+        matches!(self, Variant::SuperChip)
+    }
+}
+
+// 2.4 - Display
+// The original implementation of the Chip-8 language used a 64x32-pixel
+// monochrome display.
+const DISPLAY_WIDTH: usize = 64;
+const DISPLAY_HEIGHT: usize = 32;
+
+// Most interpreters place the built-in font data somewhere in the first
+// 512 bytes of memory, below where a ROM is loaded. 0x050 is a common
+// convention and is what we use here.
+const FONT_START_INDEX: usize = 0x50;
+const FONT_SPRITE_BYTES: u16 = 5;
+
+// The delay and sound timers count down independently of instruction
+// speed, at a fixed rate of 60Hz.
+const TIMER_INTERVAL: Duration = Duration::from_nanos(1_000_000_000 / 60);
+
+// Memory is exposed to the CPU only through this trait, so a caller can
+// swap the flat 4KB array for a bus that maps specific address ranges to
+// other devices (e.g. a memory-mapped display or keyboard) without
+// touching the opcode handlers below.
+pub trait Bus {
+    fn read(&self, addr: u16) -> u8;
+    fn write(&mut self, addr: u16, value: u8);
+}
+
+// The default Bus: a plain 4KB array, matching how most Chip-8
+// interpreters are implemented.
+pub struct FlatMemory {
+    ram: [u8; 4096],
+}
+
+impl FlatMemory {
+    pub fn new() -> Self {
+        Self { ram: [0; 4096] }
+    }
+
+    // Returns the number of bytes loaded, so a caller can log/verify it.
+    pub fn load_rom(&mut self, path: &str, start: usize) -> usize {
+        let bytes = fs::read(path).expect("Could not open file.");
+        self.set_bytes(start, &bytes);
+        bytes.len()
+    }
+
+    pub fn set_bytes(&mut self, start: usize, bytes: &[u8]) {
+        for (index, byte) in bytes.iter().enumerate() {
+            self.ram[start + index] = *byte;
+        }
+    }
+}
+
+impl Default for FlatMemory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Bus for FlatMemory {
+    fn read(&self, addr: u16) -> u8 {
+        self.ram[addr as usize]
+    }
+
+    fn write(&mut self, addr: u16, value: u8) {
+        self.ram[addr as usize] = value;
+    }
+}
+
+#[rustfmt::skip]
+const FONT_SET: [u8; 80] = [
+    0xF0, 0x90, 0x90, 0x90, 0xF0, // 0
+    0x20, 0x60, 0x20, 0x20, 0x70, // 1
+    0xF0, 0x10, 0xF0, 0x80, 0xF0, // 2
+    0xF0, 0x10, 0xF0, 0x10, 0xF0, // 3
+    0x90, 0x90, 0xF0, 0x10, 0x10, // 4
+    0xF0, 0x80, 0xF0, 0x10, 0xF0, // 5
+    0xF0, 0x80, 0xF0, 0x90, 0xF0, // 6
+    0xF0, 0x10, 0x20, 0x40, 0x40, // 7
+    0xF0, 0x90, 0xF0, 0x90, 0xF0, // 8
+    0xF0, 0x90, 0xF0, 0x10, 0xF0, // 9
+    0xF0, 0x90, 0xF0, 0x90, 0x90, // A
+    0xE0, 0x90, 0xE0, 0x90, 0xE0, // B
+    0xF0, 0x80, 0x80, 0x80, 0xF0, // C
+    0xE0, 0x90, 0x90, 0x90, 0xE0, // D
+    0xF0, 0x80, 0xF0, 0x80, 0xF0, // E
+    0xF0, 0x80, 0xF0, 0x80, 0x80, // F
+];
 
-pub struct Chip8 {
+// A decoded instruction: the standard fields (nnn, kk, x, y, n) extracted
+// once per fetch, rather than re-derived from raw bytes inside every
+// opcode handler. `run` matches on this directly.
+enum Instruction {
+    Cls,
+    Ret,
+    Jp(u16),
+    Call(u16),
+    SeVxByte(u8, u8),
+    SneVxByte(u8, u8),
+    SeVxVy(u8, u8),
+    LdVxByte(u8, u8),
+    AddVxByte(u8, u8),
+    LdVxVy(u8, u8),
+    OrVxVy(u8, u8),
+    AndVxVy(u8, u8),
+    XorVxVy(u8, u8),
+    AddVxVy(u8, u8),
+    SubVxVy(u8, u8),
+    ShrVx(u8, u8),
+    SubnVxVy(u8, u8),
+    ShlVx(u8, u8),
+    SneVxVy(u8, u8),
+    LdI(u16),
+    JpV0(u16, u8),
+    Rnd(u8, u8),
+    Drw(u8, u8, u8),
+    Skp(u8),
+    Sknp(u8),
+    LdVxDt(u8),
+    LdDtVx(u8),
+    LdStVx(u8),
+    AddIVx(u8),
+    LdFVx(u8),
+    LdBVx(u8),
+    LdIVx(u8),
+    LdVxI(u8),
+    Unknown(u8, u8),
+}
+
+pub struct Chip8<B: Bus> {
     // 2.1 - Memory
     // The Chip-8 language is capable of accessing up to 4KB (4,096 bytes) of
-    // RAM, from location 0x000 (0) to 0xFFF (4095).
-    ram: [u8; 4096],
+    // RAM, from location 0x000 (0) to 0xFFF (4095). We talk to it only
+    // through the Bus trait, so this can be a flat array or a
+    // memory-mapped device.
+    bus: B,
 
     // 2.2 - Registers
     registers: Registers,
@@ -35,67 +206,574 @@ pub struct Chip8 {
     // the interpreter shoud return to when finished with a subroutine. Chip-8
     // allows for up to 16 levels of nested subroutines.
     stack: [usize; 16],
+
+    // Chip-8 has two 8-bit pseudo-registers that are decremented at a rate of
+    // 60Hz until they reach 0.
+    delay_timer: u8,
+    sound_timer: u8,
+
+    // 2.4 - Display
+    // The display is monochrome, and can be thought of as an array of pixels
+    // that are either on (true) or off (false).
+    display: [bool; DISPLAY_WIDTH * DISPLAY_HEIGHT],
+
+    // 2.3 - Keyboard
+    // The computers which originally used the Chip-8 Language had a 16-key
+    // hexadecimal keypad, indexed here by the key's value (0-F).
+    keypad: [bool; 16],
+
+    // Not part of the spec: internal state for the Cxkk (RND) opcode's
+    // pseudo-random number generator.
+    rng_state: u32,
+
+    // Which Chip-8 derivative's quirks this interpreter follows.
+    variant: Variant,
 }
 
-impl Chip8 {
-    pub fn new() -> Self {
+impl<B: Bus> Chip8<B> {
+    pub fn new(mut bus: B, variant: Variant) -> Self {
+        for (index, byte) in FONT_SET.iter().enumerate() {
+            bus.write(FONT_START_INDEX as u16 + index as u16, *byte);
+        }
+
         Self {
-            ram: [0; 4096],
+            bus,
             registers: Registers::new(),
             pc: 0,
             sp: 0,
             stack: [0; 16],
+            delay_timer: 0,
+            sound_timer: 0,
+            display: [false; DISPLAY_WIDTH * DISPLAY_HEIGHT],
+            keypad: [false; 16],
+            rng_state: Self::seed_rng(),
+            variant,
         }
     }
 
-    pub fn load_rom(&mut self, path: &str) {
-        let bytes = fs::read(path).expect("Could not open file.");
+    // Seeds the xorshift PRNG from wall-clock time, so Cxkk (RND) doesn't
+    // produce the same sequence on every run. xorshift can't start at 0,
+    // so fall back to the old fixed seed in that one-in-a-billion case.
+    fn seed_rng() -> u32 {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.subsec_nanos())
+            .unwrap_or(0xACE1);
 
-        // TODO: Take a CLI flag for the start address to load into memory, for
-        //       now we just use the more common 0x200 start address.
-        for (index, byte) in bytes.iter().enumerate() {
-            self.ram[NORMAL_START_INDEX + index] = *byte;
+        if nanos == 0 {
+            0xACE1
+        } else {
+            nanos
         }
-
-        eprintln!("bytes loaded: {}", bytes.len());
     }
 
     pub fn run(&mut self) {
-        // TODO: Set PC start based on CLI flag for start address
-        self.pc = NORMAL_START_INDEX;
+        self.pc = self.variant.start_address();
+
+        let mut last_tick = Instant::now();
 
         loop {
-            let high_byte = self.high_byte();
-            let low_byte = self.low_byte();
+            if last_tick.elapsed() >= TIMER_INTERVAL {
+                self.tick_timers();
+                last_tick = Instant::now();
+            }
+
+            let instruction = self.decode();
 
-            match high(high_byte) {
-                0xA => {
-                    self.pc = self.load_i();
+            self.pc = match instruction {
+                Instruction::Cls => {
+                    let pc = self.cls();
+                    self.render_to_stdout();
+                    pc
                 }
-                0x6 => {
-                    self.pc = self.load_vx();
+                Instruction::Ret => self.ret(),
+                Instruction::Jp(addr) => addr as usize,
+                Instruction::Call(addr) => self.call(addr),
+                Instruction::SeVxByte(x, kk) => self.se_vx_byte(x, kk),
+                Instruction::SneVxByte(x, kk) => self.sne_vx_byte(x, kk),
+                Instruction::SeVxVy(x, y) => self.se_vx_vy(x, y),
+                Instruction::LdVxByte(x, kk) => self.load_vx(x, kk),
+                Instruction::AddVxByte(x, kk) => self.add_vx_byte(x, kk),
+                Instruction::LdVxVy(x, y) => self.ld_vx_vy(x, y),
+                Instruction::OrVxVy(x, y) => self.or_vx_vy(x, y),
+                Instruction::AndVxVy(x, y) => self.and_vx_vy(x, y),
+                Instruction::XorVxVy(x, y) => self.xor_vx_vy(x, y),
+                Instruction::AddVxVy(x, y) => self.add_vx_vy(x, y),
+                Instruction::SubVxVy(x, y) => self.sub_vx_vy(x, y),
+                Instruction::ShrVx(x, y) => self.shr_vx(x, y),
+                Instruction::SubnVxVy(x, y) => self.subn_vx_vy(x, y),
+                Instruction::ShlVx(x, y) => self.shl_vx(x, y),
+                Instruction::SneVxVy(x, y) => self.sne_vx_vy(x, y),
+                Instruction::LdI(nnn) => self.load_i(nnn),
+                Instruction::JpV0(nnn, x) => self.jp_v0(nnn, x),
+                Instruction::Rnd(x, kk) => self.rnd(x, kk),
+                Instruction::Drw(x, y, n) => {
+                    let pc = self.drw(x, y, n);
+                    self.render_to_stdout();
+                    pc
                 }
-                _ => {
+                Instruction::Skp(x) => self.skp(x),
+                Instruction::Sknp(x) => self.sknp(x),
+                Instruction::LdVxDt(x) => self.ld_vx_dt(x),
+                Instruction::LdDtVx(x) => self.ld_dt_vx(x),
+                Instruction::LdStVx(x) => self.ld_st_vx(x),
+                Instruction::AddIVx(x) => self.add_i_vx(x),
+                Instruction::LdFVx(x) => self.ld_f_vx(x),
+                Instruction::LdBVx(x) => self.ld_b_vx(x),
+                Instruction::LdIVx(x) => self.ld_i_vx(x),
+                Instruction::LdVxI(x) => self.ld_vx_i(x),
+                Instruction::Unknown(high_byte, low_byte) => {
                     eprintln!("Unrecognised instrution 0x{:x}{:x}", high_byte, low_byte);
                     break;
                 }
-            }
+            };
         }
     }
 
-    // Annn - LD I, addr
-    fn load_i(&mut self) -> usize {
-        // The value of register I is set to nnn
-        self.registers.i = self.addr();
+    // Fetches the instruction at PC and extracts the standard fields (nnn,
+    // kk, x, y, n) once, returning the decoded instruction `run` matches on.
+    fn decode(&self) -> Instruction {
+        let high_byte = self.high_byte();
+        let low_byte = self.low_byte();
+
+        let nnn = self.addr();
+        let kk = low_byte;
+        let x = low(high_byte);
+        let y = high(low_byte);
+        let n = low(low_byte);
+
+        match high(high_byte) {
+            0x0 => match low_byte {
+                0xE0 => Instruction::Cls,
+                0xEE => Instruction::Ret,
+                _ => Instruction::Unknown(high_byte, low_byte),
+            },
+            0x1 => Instruction::Jp(nnn),
+            0x2 => Instruction::Call(nnn),
+            0x3 => Instruction::SeVxByte(x, kk),
+            0x4 => Instruction::SneVxByte(x, kk),
+            0x5 => Instruction::SeVxVy(x, y),
+            0x6 => Instruction::LdVxByte(x, kk),
+            0x7 => Instruction::AddVxByte(x, kk),
+            0x8 => match n {
+                0x0 => Instruction::LdVxVy(x, y),
+                0x1 => Instruction::OrVxVy(x, y),
+                0x2 => Instruction::AndVxVy(x, y),
+                0x3 => Instruction::XorVxVy(x, y),
+                0x4 => Instruction::AddVxVy(x, y),
+                0x5 => Instruction::SubVxVy(x, y),
+                0x6 => Instruction::ShrVx(x, y),
+                0x7 => Instruction::SubnVxVy(x, y),
+                0xE => Instruction::ShlVx(x, y),
+                _ => Instruction::Unknown(high_byte, low_byte),
+            },
+            0x9 => Instruction::SneVxVy(x, y),
+            0xA => Instruction::LdI(nnn),
+            0xB => Instruction::JpV0(nnn, x),
+            0xC => Instruction::Rnd(x, kk),
+            0xD => Instruction::Drw(x, y, n),
+            0xE => match kk {
+                0x9E => Instruction::Skp(x),
+                0xA1 => Instruction::Sknp(x),
+                _ => Instruction::Unknown(high_byte, low_byte),
+            },
+            0xF => match kk {
+                0x07 => Instruction::LdVxDt(x),
+                0x15 => Instruction::LdDtVx(x),
+                0x18 => Instruction::LdStVx(x),
+                0x1E => Instruction::AddIVx(x),
+                0x29 => Instruction::LdFVx(x),
+                0x33 => Instruction::LdBVx(x),
+                0x55 => Instruction::LdIVx(x),
+                0x65 => Instruction::LdVxI(x),
+                _ => Instruction::Unknown(high_byte, low_byte),
+            },
+            _ => Instruction::Unknown(high_byte, low_byte),
+        }
+    }
+
+    // 00E0 - CLS
+    fn cls(&mut self) -> usize {
+        // Clear the display.
+        self.clear();
 
         self.pc + 2
     }
 
+    fn clear(&mut self) {
+        self.display = [false; DISPLAY_WIDTH * DISPLAY_HEIGHT];
+    }
+
+    // Decrements the delay and sound timers toward zero. Called from `run`
+    // at a fixed 60Hz, independently of how fast instructions execute.
+    fn tick_timers(&mut self) {
+        if self.delay_timer > 0 {
+            self.delay_timer -= 1;
+        }
+        if self.sound_timer > 0 {
+            self.sound_timer -= 1;
+        }
+    }
+
+    // Whether the sound timer is active, so a front end knows to beep.
+    pub fn is_sound_playing(&self) -> bool {
+        self.sound_timer > 0
+    }
+
+    // 00EE - RET
+    fn ret(&mut self) -> usize {
+        // The interpreter subtracts 1 from the stack pointer, then sets the
+        // program counter to the address it now points at (the top of the
+        // stack, since the stack pointer indexes the next free slot).
+        self.sp -= 1;
+        self.stack[self.sp]
+    }
+
+    // 2nnn - CALL addr
+    fn call(&mut self, addr: u16) -> usize {
+        // The interpreter puts the return address (the instruction after
+        // this one) in the free slot at the stack pointer, then increments
+        // the stack pointer. The PC is then set to nnn.
+        self.stack[self.sp] = self.pc + 2;
+        self.sp += 1;
+
+        addr as usize
+    }
+
+    // 3xkk - SE Vx, byte
+    fn se_vx_byte(&mut self, x: u8, kk: u8) -> usize {
+        // Skip next instruction if Vx = kk.
+        if self.registers.get(x) == kk {
+            self.pc + 4
+        } else {
+            self.pc + 2
+        }
+    }
+
+    // 4xkk - SNE Vx, byte
+    fn sne_vx_byte(&mut self, x: u8, kk: u8) -> usize {
+        // Skip next instruction if Vx != kk.
+        if self.registers.get(x) != kk {
+            self.pc + 4
+        } else {
+            self.pc + 2
+        }
+    }
+
+    // 5xy0 - SE Vx, Vy
+    fn se_vx_vy(&mut self, x: u8, y: u8) -> usize {
+        // Skip next instruction if Vx = Vy.
+        if self.registers.get(x) == self.registers.get(y) {
+            self.pc + 4
+        } else {
+            self.pc + 2
+        }
+    }
+
     // 6xkk - LD Vx, byte
-    fn load_vx(&mut self) -> usize {
+    fn load_vx(&mut self, x: u8, kk: u8) -> usize {
         // The interpreter puts the value kk into register Vx.
-        let register = low(self.high_byte());
-        self.registers.put(register, *self.low_byte());
+        self.registers.put(x, kk);
+
+        self.pc + 2
+    }
+
+    // 7xkk - ADD Vx, byte
+    fn add_vx_byte(&mut self, x: u8, kk: u8) -> usize {
+        // Adds the value kk to the value of register Vx, then stores the
+        // result in Vx. No carry flag is affected.
+        let value = self.registers.get(x).wrapping_add(kk);
+        self.registers.put(x, value);
+
+        self.pc + 2
+    }
+
+    // 8xy0 - LD Vx, Vy
+    fn ld_vx_vy(&mut self, x: u8, y: u8) -> usize {
+        // Stores the value of register Vy in register Vx.
+        self.registers.put(x, self.registers.get(y));
+
+        self.pc + 2
+    }
+
+    // 8xy1 - OR Vx, Vy
+    fn or_vx_vy(&mut self, x: u8, y: u8) -> usize {
+        let value = self.registers.get(x) | self.registers.get(y);
+        self.registers.put(x, value);
+
+        self.pc + 2
+    }
+
+    // 8xy2 - AND Vx, Vy
+    fn and_vx_vy(&mut self, x: u8, y: u8) -> usize {
+        let value = self.registers.get(x) & self.registers.get(y);
+        self.registers.put(x, value);
+
+        self.pc + 2
+    }
+
+    // 8xy3 - XOR Vx, Vy
+    fn xor_vx_vy(&mut self, x: u8, y: u8) -> usize {
+        let value = self.registers.get(x) ^ self.registers.get(y);
+        self.registers.put(x, value);
+
+        self.pc + 2
+    }
+
+    // 8xy4 - ADD Vx, Vy
+    fn add_vx_vy(&mut self, x: u8, y: u8) -> usize {
+        // The values of Vx and Vy are added together. If the result is
+        // greater than 8 bits, VF is set to 1, otherwise 0.
+        let (value, carry) = self.registers.get(x).overflowing_add(self.registers.get(y));
+        self.registers.put(x, value);
+        self.registers.put(0xF, carry as u8);
+
+        self.pc + 2
+    }
+
+    // 8xy5 - SUB Vx, Vy
+    fn sub_vx_vy(&mut self, x: u8, y: u8) -> usize {
+        // If Vx > Vy, then VF is set to 1, otherwise 0. Then Vy is
+        // subtracted from Vx, and the results stored in Vx.
+        let vx = self.registers.get(x);
+        let vy = self.registers.get(y);
+        self.registers.put(x, vx.wrapping_sub(vy));
+        self.registers.put(0xF, (vx > vy) as u8);
+
+        self.pc + 2
+    }
+
+    // 8xy6 - SHR Vx {, Vy}
+    fn shr_vx(&mut self, x: u8, y: u8) -> usize {
+        // If the least-significant bit of the shifted value is 1, then VF
+        // is set to 1, otherwise 0. The result is then stored in Vx. The
+        // COSMAC VIP shifts Vy; SUPER-CHIP shifts Vx in place.
+        let source = if self.variant.shift_in_place() {
+            self.registers.get(x)
+        } else {
+            self.registers.get(y)
+        };
+        self.registers.put(x, source >> 1);
+        self.registers.put(0xF, source & 0x1);
+
+        self.pc + 2
+    }
+
+    // 8xy7 - SUBN Vx, Vy
+    fn subn_vx_vy(&mut self, x: u8, y: u8) -> usize {
+        // If Vy > Vx, then VF is set to 1, otherwise 0. Then Vx is
+        // subtracted from Vy, and the results stored in Vx.
+        let vx = self.registers.get(x);
+        let vy = self.registers.get(y);
+        self.registers.put(x, vy.wrapping_sub(vx));
+        self.registers.put(0xF, (vy > vx) as u8);
+
+        self.pc + 2
+    }
+
+    // 8xyE - SHL Vx {, Vy}
+    fn shl_vx(&mut self, x: u8, y: u8) -> usize {
+        // If the most-significant bit of the shifted value is 1, then VF
+        // is set to 1, otherwise 0. The result is then stored in Vx. The
+        // COSMAC VIP shifts Vy; SUPER-CHIP shifts Vx in place.
+        let source = if self.variant.shift_in_place() {
+            self.registers.get(x)
+        } else {
+            self.registers.get(y)
+        };
+        self.registers.put(x, source << 1);
+        self.registers.put(0xF, (source >> 7) & 0x1);
+
+        self.pc + 2
+    }
+
+    // 9xy0 - SNE Vx, Vy
+    fn sne_vx_vy(&mut self, x: u8, y: u8) -> usize {
+        // Skip next instruction if Vx != Vy.
+        if self.registers.get(x) != self.registers.get(y) {
+            self.pc + 4
+        } else {
+            self.pc + 2
+        }
+    }
+
+    // Annn - LD I, addr
+    fn load_i(&mut self, nnn: u16) -> usize {
+        // The value of register I is set to nnn
+        self.registers.i = nnn;
+
+        self.pc + 2
+    }
+
+    // Bnnn - JP V0, addr
+    fn jp_v0(&mut self, nnn: u16, x: u8) -> usize {
+        // The program counter is set to nnn plus the value of V0. SUPER-CHIP
+        // instead uses Vx, where x is the highest nibble of nnn.
+        let offset = if self.variant.jump_uses_vx() {
+            self.registers.get(x)
+        } else {
+            self.registers.get(0x0)
+        };
+
+        nnn as usize + offset as usize
+    }
+
+    // Cxkk - RND Vx, byte
+    fn rnd(&mut self, x: u8, kk: u8) -> usize {
+        // The interpreter generates a random number from 0 to 255, which is
+        // then ANDed with the value kk. The results are stored in Vx.
+        let value = self.rand_byte() & kk;
+        self.registers.put(x, value);
+
+        self.pc + 2
+    }
+
+    // Dxyn - DRW Vx, Vy, nibble
+    fn drw(&mut self, x: u8, y: u8, n: u8) -> usize {
+        // The interpreter reads n bytes from memory, starting at the
+        // address stored in I. These bytes are then displayed as sprites
+        // on screen at coordinates (Vx, Vy), XORed onto the existing
+        // display. If this causes any pixels to be erased, VF is set to 1,
+        // otherwise it is set to 0.
+        let vx = self.registers.get(x) as usize;
+        let vy = self.registers.get(y) as usize;
+
+        let mut collision = false;
+
+        for row in 0..n as usize {
+            let sprite_byte = self.bus.read(self.registers.i + row as u16);
+
+            for col in 0..8 {
+                let sprite_pixel = (sprite_byte >> (7 - col)) & 0x1;
+                if sprite_pixel == 0 {
+                    continue;
+                }
+
+                let px = (vx + col) % DISPLAY_WIDTH;
+                let py = (vy + row) % DISPLAY_HEIGHT;
+                let index = py * DISPLAY_WIDTH + px;
+
+                if self.display[index] {
+                    collision = true;
+                }
+                self.display[index] ^= true;
+            }
+        }
+
+        self.registers.put(0xF, collision as u8);
+
+        self.pc + 2
+    }
+
+    // Ex9E - SKP Vx
+    fn skp(&mut self, x: u8) -> usize {
+        // Skip next instruction if key with the value of Vx is pressed. Vx
+        // can hold any byte, but the keypad only has 16 keys, so mask it
+        // down to the valid range instead of indexing out of bounds.
+        let key = (self.registers.get(x) & 0xF) as usize;
+        if self.keypad[key] {
+            self.pc + 4
+        } else {
+            self.pc + 2
+        }
+    }
+
+    // ExA1 - SKNP Vx
+    fn sknp(&mut self, x: u8) -> usize {
+        // Skip next instruction if key with the value of Vx is not pressed.
+        let key = (self.registers.get(x) & 0xF) as usize;
+        if !self.keypad[key] {
+            self.pc + 4
+        } else {
+            self.pc + 2
+        }
+    }
+
+    // Fx07 - LD Vx, DT
+    fn ld_vx_dt(&mut self, x: u8) -> usize {
+        // The value of DT is placed into Vx.
+        self.registers.put(x, self.delay_timer);
+
+        self.pc + 2
+    }
+
+    // Fx15 - LD DT, Vx
+    fn ld_dt_vx(&mut self, x: u8) -> usize {
+        // DT is set equal to the value of Vx.
+        self.delay_timer = self.registers.get(x);
+
+        self.pc + 2
+    }
+
+    // Fx18 - LD ST, Vx
+    fn ld_st_vx(&mut self, x: u8) -> usize {
+        // ST is set equal to the value of Vx.
+        self.sound_timer = self.registers.get(x);
+
+        self.pc + 2
+    }
+
+    // Fx1E - ADD I, Vx
+    fn add_i_vx(&mut self, x: u8) -> usize {
+        // The values of I and Vx are added, and the results are stored in I.
+        self.registers.i = self.registers.i.wrapping_add(self.registers.get(x) as u16);
+
+        self.pc + 2
+    }
+
+    // Fx29 - LD F, Vx
+    fn ld_f_vx(&mut self, x: u8) -> usize {
+        // The value of I is set to the location for the hexadecimal sprite
+        // corresponding to the value of Vx.
+        let digit = self.registers.get(x) as u16;
+        self.registers.i = FONT_START_INDEX as u16 + digit * FONT_SPRITE_BYTES;
+
+        self.pc + 2
+    }
+
+    // Fx33 - LD B, Vx
+    fn ld_b_vx(&mut self, x: u8) -> usize {
+        // The interpreter takes the decimal value of Vx, and places the
+        // hundreds digit in memory at location I, the tens digit at
+        // location I+1, and the ones digit at location I+2.
+        let value = self.registers.get(x);
+        let i = self.registers.i;
+
+        self.bus.write(i, value / 100);
+        self.bus.write(i + 1, (value / 10) % 10);
+        self.bus.write(i + 2, value % 10);
+
+        self.pc + 2
+    }
+
+    // Fx55 - LD [I], Vx
+    fn ld_i_vx(&mut self, x: u8) -> usize {
+        // The interpreter copies the values of registers V0 through Vx into
+        // memory, starting at the address in I.
+        let i = self.registers.i;
+
+        for offset in 0..=x as u16 {
+            self.bus.write(i + offset, self.registers.get(offset as u8));
+        }
+        if self.variant.load_store_increments_i() {
+            self.registers.i += x as u16 + 1;
+        }
+
+        self.pc + 2
+    }
+
+    // Fx65 - LD Vx, [I]
+    fn ld_vx_i(&mut self, x: u8) -> usize {
+        // The interpreter reads values from memory starting at location I
+        // into registers V0 through Vx.
+        let i = self.registers.i;
+
+        for offset in 0..=x as u16 {
+            self.registers.put(offset as u8, self.bus.read(i + offset));
+        }
+        if self.variant.load_store_increments_i() {
+            self.registers.i += x as u16 + 1;
+        }
 
         self.pc + 2
     }
@@ -104,12 +782,12 @@ impl Chip8 {
     // All instructions are 2 bytes long and are stored
     // most-significant-byte first. In memory, the first byte of each
     // instruction should be located at an even addresses.
-    fn high_byte(&self) -> &u8 {
-        &self.ram[self.pc]
+    fn high_byte(&self) -> u8 {
+        self.bus.read(self.pc as u16)
     }
 
-    fn low_byte(&self) -> &u8 {
-        &self.ram[self.pc + 1]
+    fn low_byte(&self) -> u8 {
+        self.bus.read(self.pc as u16 + 1)
     }
 
     fn addr(&self) -> u16 {
@@ -118,12 +796,39 @@ impl Chip8 {
     }
 
     fn instruction(&self) -> u16 {
-        ((*self.high_byte() as u16) << 8) | *self.low_byte() as u16
+        ((self.high_byte() as u16) << 8) | self.low_byte() as u16
+    }
+
+    // A small xorshift PRNG, good enough to seed Cxkk (RND) without pulling
+    // in an external crate.
+    fn rand_byte(&mut self) -> u8 {
+        let mut x = self.rng_state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.rng_state = x;
+
+        (x & 0xFF) as u8
+    }
+
+    // Renders the framebuffer as ASCII art, one character per pixel, so the
+    // display can be observed from a terminal during `run`.
+    pub fn render_to_stdout(&self) {
+        for row in 0..DISPLAY_HEIGHT {
+            for col in 0..DISPLAY_WIDTH {
+                let pixel = self.display[row * DISPLAY_WIDTH + col];
+                print!("{}", if pixel { '#' } else { ' ' });
+            }
+            println!();
+        }
     }
 
     pub fn dump_to_stdout(&self) {
         println!("=== MEMORY ===");
-        for line in self.ram.chunks(64) {
+        for row in 0..64 {
+            let line: Vec<u8> = (0..64)
+                .map(|col| self.bus.read((row * 64 + col) as u16))
+                .collect();
             for instruction in line.chunks(2) {
                 print!("{:02X}{:02X} ", instruction[0], instruction[1]);
             }
@@ -142,12 +847,25 @@ impl Chip8 {
     }
 }
 
-fn high(byte: &u8) -> u8 {
+// Loading a ROM from disk is a `FlatMemory`-specific concern (a
+// memory-mapped `Bus` may have no notion of "a contiguous run of file
+// bytes"), so this delegates to `FlatMemory::load_rom` rather than living
+// on the generic `impl<B: Bus> Chip8<B>` above.
+impl Chip8<FlatMemory> {
+    pub fn load_rom(&mut self, path: &str) {
+        let start = self.variant.start_address();
+        let bytes_loaded = self.bus.load_rom(path, start);
+
+        eprintln!("bytes loaded: {}", bytes_loaded);
+    }
+}
+
+fn high(byte: u8) -> u8 {
     let mask = (1 << 4) - 1;
     (byte & mask << 4) >> 4
 }
 
-fn low(byte: &u8) -> u8 {
+fn low(byte: u8) -> u8 {
     let mask = (1 << 4) - 1;
     byte & mask
 }
@@ -155,23 +873,9 @@ fn low(byte: &u8) -> u8 {
 // 2.2 - Registers
 pub struct Registers {
     // Chip-8 has 16 general purpose 8-bit registers, usually referred to as Vx,
-    // where x is a hexadecimal digit (0 through F).
-    v_0: u8,
-    v_1: u8,
-    v_2: u8,
-    v_3: u8,
-    v_4: u8,
-    v_5: u8,
-    v_6: u8,
-    v_7: u8,
-    v_8: u8,
-    v_9: u8,
-    v_a: u8,
-    v_b: u8,
-    v_c: u8,
-    v_d: u8,
-    v_e: u8,
-    v_f: u8,
+    // where x is a hexadecimal digit (0 through F), indexed directly by the
+    // nibble the opcode decodes it from.
+    v: [u8; 16],
 
     // There is also a 16-bit register called I. This register is generally
     // used to store memory addresses
@@ -180,70 +884,193 @@ pub struct Registers {
 
 impl Registers {
     pub fn new() -> Self {
-        Self {
-            v_0: 0,
-            v_1: 0,
-            v_2: 0,
-            v_3: 0,
-            v_4: 0,
-            v_5: 0,
-            v_6: 0,
-            v_7: 0,
-            v_8: 0,
-            v_9: 0,
-            v_a: 0,
-            v_b: 0,
-            v_c: 0,
-            v_d: 0,
-            v_e: 0,
-            v_f: 0,
-            i: 0,
-        }
+        Self { v: [0; 16], i: 0 }
     }
 
     pub fn put(&mut self, register: u8, value: u8) {
-        match register {
-            0x0 => self.v_0 = value,
-            0x1 => self.v_1 = value,
-            0x2 => self.v_2 = value,
-            0x3 => self.v_3 = value,
-            0x4 => self.v_4 = value,
-            0x5 => self.v_5 = value,
-            0x6 => self.v_6 = value,
-            0x7 => self.v_7 = value,
-            0x8 => self.v_8 = value,
-            0x9 => self.v_9 = value,
-            0xa => self.v_a = value,
-            0xb => self.v_b = value,
-            0xc => self.v_c = value,
-            0xd => self.v_d = value,
-            0xe => self.v_e = value,
-            0xf => self.v_f = value,
-            _ => panic!(
-                "Tried to set a register that doesn't exist v_{:x}",
-                register
-            ),
-        }
+        self.v[register as usize] = value;
+    }
+
+    pub fn get(&self, register: u8) -> u8 {
+        self.v[register as usize]
     }
 
     pub fn dump_to_stdout(&self) {
-        print!("v_0: {:02X} ", self.v_0);
-        print!("v_1: {:02X} ", self.v_1);
-        print!("v_2: {:02X} ", self.v_2);
-        print!("v_3: {:02X} ", self.v_3);
-        print!("v_4: {:02X} ", self.v_4);
-        print!("v_5: {:02X} ", self.v_5);
-        print!("v_6: {:02X} ", self.v_6);
-        print!("v_7: {:02X} ", self.v_7);
-        print!("v_8: {:02X} ", self.v_8);
-        print!("v_9: {:02X} ", self.v_9);
-        print!("v_a: {:02X} ", self.v_a);
-        print!("v_b: {:02X} ", self.v_b);
-        print!("v_c: {:02X} ", self.v_c);
-        print!("v_d: {:02X} ", self.v_d);
-        print!("v_e: {:02X} ", self.v_e);
-        print!("v_f: {:02X} ", self.v_f);
+        for (register, value) in self.v.iter().enumerate() {
+            print!("v_{:x}: {:02X} ", register, value);
+        }
         println!();
         println!("i: {:04X}", self.i)
     }
 }
+
+impl Default for Registers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_chip8() -> Chip8<FlatMemory> {
+        Chip8::new(FlatMemory::new(), Variant::CosmacVip)
+    }
+
+    fn new_chip8_with(variant: Variant) -> Chip8<FlatMemory> {
+        Chip8::new(FlatMemory::new(), variant)
+    }
+
+    #[test]
+    fn skp_masks_an_out_of_range_vx_into_the_keypad() {
+        let mut chip8 = new_chip8();
+        chip8.registers.put(0x0, 0x20); // out of the keypad's 0..16 range
+        chip8.keypad[0x0] = true;
+        let pc_before = chip8.pc;
+
+        assert_eq!(chip8.skp(0x0), pc_before + 4);
+    }
+
+    #[test]
+    fn sknp_masks_an_out_of_range_vx_into_the_keypad() {
+        let mut chip8 = new_chip8();
+        chip8.registers.put(0x0, 0x20);
+        chip8.keypad[0x0] = false;
+        let pc_before = chip8.pc;
+
+        assert_eq!(chip8.sknp(0x0), pc_before + 4);
+    }
+
+    #[test]
+    fn rand_byte_churns_instead_of_repeating() {
+        let mut chip8 = new_chip8();
+        chip8.rng_state = 0xACE1;
+
+        let a = chip8.rand_byte();
+        let b = chip8.rand_byte();
+
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn add_vx_vy_sets_vf_on_overflow() {
+        let mut chip8 = new_chip8();
+        chip8.registers.put(0x0, 0xFF);
+        chip8.registers.put(0x1, 0x02);
+
+        chip8.add_vx_vy(0x0, 0x1);
+
+        assert_eq!(chip8.registers.get(0x0), 0x01);
+        assert_eq!(chip8.registers.get(0xF), 1);
+    }
+
+    #[test]
+    fn add_vx_vy_clears_vf_without_overflow() {
+        let mut chip8 = new_chip8();
+        chip8.registers.put(0x0, 0x01);
+        chip8.registers.put(0x1, 0x02);
+
+        chip8.add_vx_vy(0x0, 0x1);
+
+        assert_eq!(chip8.registers.get(0x0), 0x03);
+        assert_eq!(chip8.registers.get(0xF), 0);
+    }
+
+    #[test]
+    fn sub_vx_vy_sets_vf_when_vx_is_greater() {
+        let mut chip8 = new_chip8();
+        chip8.registers.put(0x0, 0x05);
+        chip8.registers.put(0x1, 0x02);
+
+        chip8.sub_vx_vy(0x0, 0x1);
+
+        assert_eq!(chip8.registers.get(0x0), 0x03);
+        assert_eq!(chip8.registers.get(0xF), 1);
+    }
+
+    #[test]
+    fn sub_vx_vy_clears_vf_when_vy_is_greater() {
+        let mut chip8 = new_chip8();
+        chip8.registers.put(0x0, 0x02);
+        chip8.registers.put(0x1, 0x05);
+
+        chip8.sub_vx_vy(0x0, 0x1);
+
+        assert_eq!(chip8.registers.get(0x0), 0xFD);
+        assert_eq!(chip8.registers.get(0xF), 0);
+    }
+
+    #[test]
+    fn shr_vx_stores_the_shifted_out_bit_in_vf() {
+        let mut chip8 = new_chip8();
+        chip8.registers.put(0x0, 0x03);
+
+        chip8.shr_vx(0x0, 0x0);
+
+        assert_eq!(chip8.registers.get(0x0), 0x01);
+        assert_eq!(chip8.registers.get(0xF), 1);
+    }
+
+    #[test]
+    fn shr_vx_shifts_vy_on_cosmac_vip() {
+        let mut chip8 = new_chip8_with(Variant::CosmacVip);
+        chip8.registers.put(0x0, 0xFF);
+        chip8.registers.put(0x1, 0x02);
+
+        chip8.shr_vx(0x0, 0x1);
+
+        assert_eq!(chip8.registers.get(0x0), 0x01);
+    }
+
+    #[test]
+    fn shr_vx_shifts_vx_in_place_on_super_chip() {
+        let mut chip8 = new_chip8_with(Variant::SuperChip);
+        chip8.registers.put(0x0, 0x04);
+        chip8.registers.put(0x1, 0xFF);
+
+        chip8.shr_vx(0x0, 0x1);
+
+        assert_eq!(chip8.registers.get(0x0), 0x02);
+    }
+
+    #[test]
+    fn ld_i_vx_increments_i_on_cosmac_vip() {
+        let mut chip8 = new_chip8_with(Variant::CosmacVip);
+        chip8.registers.i = 0x300;
+
+        chip8.ld_i_vx(0x3);
+
+        assert_eq!(chip8.registers.i, 0x304);
+    }
+
+    #[test]
+    fn ld_i_vx_leaves_i_unchanged_on_super_chip() {
+        let mut chip8 = new_chip8_with(Variant::SuperChip);
+        chip8.registers.i = 0x300;
+
+        chip8.ld_i_vx(0x3);
+
+        assert_eq!(chip8.registers.i, 0x300);
+    }
+
+    #[test]
+    fn ld_vx_i_increments_i_on_cosmac_vip() {
+        let mut chip8 = new_chip8_with(Variant::CosmacVip);
+        chip8.registers.i = 0x300;
+
+        chip8.ld_vx_i(0x3);
+
+        assert_eq!(chip8.registers.i, 0x304);
+    }
+
+    #[test]
+    fn ld_vx_i_leaves_i_unchanged_on_super_chip() {
+        let mut chip8 = new_chip8_with(Variant::SuperChip);
+        chip8.registers.i = 0x300;
+
+        chip8.ld_vx_i(0x3);
+
+        assert_eq!(chip8.registers.i, 0x300);
+    }
+}